@@ -1,7 +1,10 @@
 use std::env;
 
+use std::time::Duration;
+
 use amf::Amf3Value;
-use egui::{DragValue, ScrollArea, TextEdit};
+use egui::{CollapsingHeader, DragValue, ScrollArea, TextEdit};
+use soledit::selector::{for_each_match, Selector};
 use sfml::{
     graphics::{Color, RenderTarget, RenderWindow},
     window::{Event, Style},
@@ -9,7 +12,13 @@ use sfml::{
 
 fn main() {
     let path = env::args_os().nth(1).expect("Need file path as argument");
-    let mut sol = soledit::read_from_file(path.as_ref()).unwrap();
+    let mut sol = match soledit::read_from_file(path.as_ref()) {
+        Ok(sol) => sol,
+        Err(e) => {
+            eprintln!("Failed to open {}: {e}", path.to_string_lossy());
+            std::process::exit(1);
+        }
+    };
     let mut window = RenderWindow::new((640, 480), "SolEdit", Style::CLOSE, &Default::default());
     window.set_vertical_sync_enabled(true);
     let mut sf_egui = egui_sfml::SfEgui::new(&window);
@@ -42,72 +51,267 @@ fn main() {
     sol.write_to_file(path.as_ref()).unwrap();
 }
 
+/// Compile the filter into a selector. A parse error (e.g. a half-typed path)
+/// is shown inline in red and `None` is returned, so no values are listed
+/// until the path parses.
+fn compile_filter(ui: &mut egui::Ui, filter_string: &str) -> Option<Selector> {
+    match Selector::parse(filter_string) {
+        Ok(selector) => Some(selector),
+        Err(e) => {
+            ui.colored_label(egui::Color32::RED, e.to_string());
+            None
+        }
+    }
+}
+
 fn ui_amf3(
     ui: &mut egui::Ui,
     root_object: &mut [soledit::Pair<soledit::Amf3Value>],
     filter_string: &str,
 ) {
+    let Some(selector) = compile_filter(ui, filter_string) else {
+        return;
+    };
     ScrollArea::vertical().show(ui, |ui| {
-        for pair in root_object {
-            if !pair.key.contains(filter_string) {
-                continue;
+        let mut id = 0usize;
+        for_each_match(root_object, &selector, &mut |path, key, value| {
+            if let Some(key) = key {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(key);
+                });
             }
-            ui.horizontal(|ui| {
-                ui.text_edit_singleline(&mut pair.key);
-                match &mut pair.value {
-                    Amf3Value::Undefined => todo!(),
-                    Amf3Value::Null => ui.label("null"),
-                    Amf3Value::Boolean(b) => ui.checkbox(b, ""),
-                    Amf3Value::Integer(n) => ui.add(DragValue::new(n)),
-                    Amf3Value::Double(n) => ui.add(DragValue::new(n)),
-                    Amf3Value::String(s) => ui.text_edit_singleline(s),
-                    Amf3Value::XmlDocument(_) => todo!(),
-                    Amf3Value::Date { unix_time } => ui.label("<date>"),
-                    Amf3Value::Array {
-                        assoc_entries,
-                        dense_entries,
-                    } => ui.label("<array>"),
-                    Amf3Value::Object {
-                        class_name,
-                        sealed_count,
-                        entries,
-                    } => ui.label("<object>"),
-                    Amf3Value::Xml(_) => todo!(),
-                    Amf3Value::ByteArray(_) => todo!(),
-                    Amf3Value::IntVector { is_fixed, entries } => todo!(),
-                    Amf3Value::UintVector { is_fixed, entries } => todo!(),
-                    Amf3Value::DoubleVector { is_fixed, entries } => todo!(),
-                    Amf3Value::ObjectVector {
-                        class_name,
-                        is_fixed,
-                        entries,
-                    } => todo!(),
-                    Amf3Value::Dictionary { is_weak, entries } => todo!(),
-                }
+            amf3_widget(ui, &mut id, &path.to_string(), value);
+        });
+    });
+}
+
+/// A default value used when adding a new array/dictionary entry.
+fn default_amf3() -> Amf3Value {
+    Amf3Value::Null
+}
+
+/// Recursively render an editor for an AMF3 value. Scalars get an inline
+/// editor; `Object`/`Array`/`Dictionary` expand into collapsible sub-trees;
+/// `ByteArray` gets a hex editor and `Date` a seconds/nanoseconds editor.
+fn amf3_widget(ui: &mut egui::Ui, id: &mut usize, label: &str, value: &mut Amf3Value) {
+    let my_id = *id;
+    *id += 1;
+    match value {
+        Amf3Value::Undefined => inline(ui, label, |ui| {
+            ui.label("undefined");
+        }),
+        Amf3Value::Null => inline(ui, label, |ui| {
+            ui.label("null");
+        }),
+        Amf3Value::Boolean(b) => inline(ui, label, |ui| {
+            ui.checkbox(b, "");
+        }),
+        Amf3Value::Integer(n) => inline(ui, label, |ui| {
+            ui.add(DragValue::new(n));
+        }),
+        Amf3Value::Double(n) => inline(ui, label, |ui| {
+            ui.add(DragValue::new(n));
+        }),
+        Amf3Value::String(s) => inline(ui, label, |ui| {
+            ui.text_edit_singleline(s);
+        }),
+        Amf3Value::Xml(s) | Amf3Value::XmlDocument(s) => inline(ui, label, |ui| {
+            ui.add(TextEdit::multiline(s));
+        }),
+        Amf3Value::ByteArray(bytes) => inline(ui, label, |ui| {
+            hex_editor(ui, my_id, bytes);
+        }),
+        Amf3Value::Date { unix_time } => inline(ui, label, |ui| {
+            date_editor(ui, unix_time);
+        }),
+        Amf3Value::Object {
+            class_name,
+            entries,
+            ..
+        } => {
+            let heading = match class_name {
+                Some(name) => format!("{label}  ({name})"),
+                None => label.to_owned(),
+            };
+            CollapsingHeader::new(heading)
+                .id_source(my_id)
+                .show(ui, |ui| {
+                    for pair in entries.iter_mut() {
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut pair.key);
+                        });
+                        amf3_widget(ui, id, &pair.key.clone(), &mut pair.value);
+                    }
+                });
+        }
+        Amf3Value::Array {
+            assoc_entries,
+            dense_entries,
+        } => {
+            CollapsingHeader::new(format!("{label}  [array]"))
+                .id_source(my_id)
+                .show(ui, |ui| {
+                    for pair in assoc_entries.iter_mut() {
+                        amf3_widget(ui, id, &pair.key.clone(), &mut pair.value);
+                    }
+                    let mut remove = None;
+                    for (i, value) in dense_entries.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("x").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                        amf3_widget(ui, id, &format!("[{i}]"), value);
+                    }
+                    if let Some(i) = remove {
+                        dense_entries.remove(i);
+                    }
+                    if ui.button("+ add element").clicked() {
+                        dense_entries.push(default_amf3());
+                    }
+                });
+        }
+        Amf3Value::Dictionary { is_weak, entries } => {
+            CollapsingHeader::new(format!("{label}  [dictionary]"))
+                .id_source(my_id)
+                .show(ui, |ui| {
+                    ui.checkbox(is_weak, "weak keys");
+                    let mut remove = None;
+                    for (i, (key, val)) in entries.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("x").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                        amf3_widget(ui, id, &format!("key[{i}]"), key);
+                        amf3_widget(ui, id, &format!("value[{i}]"), val);
+                    }
+                    if let Some(i) = remove {
+                        entries.remove(i);
+                    }
+                    if ui.button("+ add entry").clicked() {
+                        entries.push((default_amf3(), default_amf3()));
+                    }
+                });
+        }
+        Amf3Value::IntVector { is_fixed, entries } => {
+            vector_widget(ui, my_id, label, is_fixed, entries, |ui, n| {
+                ui.add(DragValue::new(n));
+            });
+        }
+        Amf3Value::UintVector { is_fixed, entries } => {
+            vector_widget(ui, my_id, label, is_fixed, entries, |ui, n| {
+                ui.add(DragValue::new(n));
             });
         }
+        Amf3Value::DoubleVector { is_fixed, entries } => {
+            vector_widget(ui, my_id, label, is_fixed, entries, |ui, n| {
+                ui.add(DragValue::new(n));
+            });
+        }
+        Amf3Value::ObjectVector {
+            class_name,
+            is_fixed,
+            entries,
+        } => {
+            let heading = match class_name {
+                Some(name) => format!("{label}  [vector {name}]"),
+                None => format!("{label}  [object vector]"),
+            };
+            CollapsingHeader::new(heading)
+                .id_source(my_id)
+                .show(ui, |ui| {
+                    ui.checkbox(is_fixed, "fixed length");
+                    for (i, value) in entries.iter_mut().enumerate() {
+                        amf3_widget(ui, id, &format!("[{i}]"), value);
+                    }
+                });
+        }
+    }
+}
+
+/// Render `label: <widget>` on a single row.
+fn inline(ui: &mut egui::Ui, label: &str, widget: impl FnOnce(&mut egui::Ui)) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        widget(ui);
     });
 }
 
+fn vector_widget<T>(
+    ui: &mut egui::Ui,
+    id: usize,
+    label: &str,
+    is_fixed: &mut bool,
+    entries: &mut [T],
+    mut editor: impl FnMut(&mut egui::Ui, &mut T),
+) {
+    CollapsingHeader::new(format!("{label}  [vector]"))
+        .id_source(id)
+        .show(ui, |ui| {
+            ui.checkbox(is_fixed, "fixed length");
+            for entry in entries.iter_mut() {
+                editor(ui, entry);
+            }
+        });
+}
+
+/// Edit a `ByteArray` as a hex string, keeping the in-progress text in egui's
+/// temporary memory so invalid intermediate input isn't discarded each frame.
+fn hex_editor(ui: &mut egui::Ui, id: usize, bytes: &mut Vec<u8>) {
+    let edit_id = ui.id().with(("hex", id));
+    let mut buf = ui
+        .data_mut(|d| d.get_temp::<String>(edit_id))
+        .unwrap_or_else(|| soledit::hex::encode(bytes));
+    let response = ui.add(TextEdit::singleline(&mut buf).hint_text("hex bytes"));
+    if response.changed() {
+        if let Some(decoded) = soledit::hex::decode(&buf) {
+            *bytes = decoded;
+        }
+        ui.data_mut(|d| d.insert_temp(edit_id, buf));
+    }
+}
+
+fn date_editor(ui: &mut egui::Ui, unix_time: &mut Duration) {
+    let mut secs = unix_time.as_secs();
+    let mut nanos = unix_time.subsec_nanos();
+    let r1 = ui.add(DragValue::new(&mut secs).prefix("s: "));
+    let r2 = ui.add(DragValue::new(&mut nanos).prefix("ns: "));
+    if r1.changed() || r2.changed() {
+        *unix_time = Duration::new(secs, nanos);
+    }
+}
+
 fn ui_amf0(
     ui: &mut egui::Ui,
     root_object: &mut [soledit::Pair<soledit::Amf0Value>],
     filter_string: &str,
 ) {
+    let Some(selector) = compile_filter(ui, filter_string) else {
+        return;
+    };
     ScrollArea::vertical().show(ui, |ui| {
-        for pair in root_object {
-            if !pair.key.contains(filter_string) {
-                continue;
-            }
+        for_each_match(root_object, &selector, &mut |path, key, value| {
             ui.horizontal(|ui| {
-                ui.text_edit_singleline(&mut pair.key);
-                match &mut pair.value {
-                    soledit::Amf0Value::Num(n) => ui.add(DragValue::new(n)),
-                    soledit::Amf0Value::Bool(b) => ui.checkbox(b, ""),
-                    soledit::Amf0Value::String(s) => ui.text_edit_singleline(s),
-                    soledit::Amf0Value::Object(_) => todo!(),
+                match key {
+                    Some(key) => {
+                        ui.text_edit_singleline(key);
+                    }
+                    None => {
+                        ui.label(path.to_string());
+                    }
                 }
+                amf0_leaf(ui, value);
             });
-        }
+        });
     });
 }
+
+fn amf0_leaf(ui: &mut egui::Ui, value: &mut soledit::Amf0Value) {
+    match value {
+        soledit::Amf0Value::Num(n) => ui.add(DragValue::new(n)),
+        soledit::Amf0Value::Bool(b) => ui.checkbox(b, ""),
+        soledit::Amf0Value::String(s) => ui.text_edit_singleline(s),
+        soledit::Amf0Value::Object(_) => ui.label("<object>"),
+    };
+}
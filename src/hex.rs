@@ -0,0 +1,36 @@
+//! Hex encoding/decoding for `ByteArray` values.
+//!
+//! The JSON, text and GUI representations all spell a `ByteArray` as a hex
+//! string; this is the single implementation they share. [`decode`] returns an
+//! `Option` so each caller can map failure onto its own error type.
+
+/// Encode bytes as a lowercase hex string.
+pub fn encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        s.push(char::from_digit((byte >> 4) as u32, 16).unwrap());
+        s.push(char::from_digit((byte & 0xf) as u32, 16).unwrap());
+    }
+    s
+}
+
+/// Decode a hex string, ignoring surrounding whitespace. Returns `None` on an
+/// odd length or a non-hex digit.
+///
+/// Works over bytes rather than `&str` slicing: a multibyte UTF-8 char isn't
+/// valid hex either way, but slicing into the middle of one panics instead of
+/// just failing to parse.
+pub fn decode(s: &str) -> Option<Vec<u8>> {
+    let bytes = s.trim().as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16)?;
+            let lo = (pair[1] as char).to_digit(16)?;
+            Some((hi * 16 + lo) as u8)
+        })
+        .collect()
+}
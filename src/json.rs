@@ -0,0 +1,590 @@
+//! Lossless, reversible JSON mapping for `.sol` files.
+//!
+//! The point of this module is to let users pull a save file out into plain
+//! text, diff/patch it in an editor or a script, and push it back into a valid
+//! `.sol`. AMF objects become JSON objects and dense arrays become JSON arrays,
+//! but everything that has no natural JSON counterpart (`Date`, `ByteArray`,
+//! the typed vectors, dictionaries, …) is written as a tagged wrapper object of
+//! the form `{ "$date": … }` so the mapping stays reversible.
+//!
+//! `Amf3Value` and `Pair` live in the `amf` crate, so the orphan rule keeps us
+//! from implementing `serde` directly on them; instead the conversions below go
+//! through [`serde_json::Value`], which also pins down the exact wire shape the
+//! tags use. The local [`Amf0Value`]/[`SolVariant`] do get real `serde` impls,
+//! expressed in terms of the same conversions. If you need an `Amf0Value`,
+//! `Amf3Value` or [`Pair`] as a field of your own `#[derive(Serialize,
+//! Deserialize)]` type, wrap it in [`ValueJson`]/[`PairJson`] — they implement
+//! `serde` the same way, just behind a local newtype the orphan rule allows.
+
+use crate::{hex, Amf0Value, Amf3Value, Pair, Sol, SolVariant};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Map, Value as Json};
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+impl SolVariant {
+    /// Read a `.sol` back from its JSON representation.
+    pub fn read_from_json(path: &Path) -> Result<SolVariant, Box<dyn Error>> {
+        let text = std::fs::read_to_string(path)?;
+        let json: Json = serde_json::from_str(&text)?;
+        Ok(sol_from_json(&json)?)
+    }
+    /// Write this `.sol` out as pretty-printed JSON.
+    pub fn write_to_json(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = sol_to_json(self);
+        std::fs::write(path, serde_json::to_vec_pretty(&json)?)?;
+        Ok(())
+    }
+}
+
+/// Read a `.sol` from its JSON representation. Mirrors [`crate::read_from_file`].
+pub fn read_from_json(path: &Path) -> Result<SolVariant, Box<dyn Error>> {
+    SolVariant::read_from_json(path)
+}
+
+impl Serialize for SolVariant {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        sol_to_json(self).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for SolVariant {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = Json::deserialize(d)?;
+        sol_from_json(&json).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Amf0Value {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        amf0_to_json(self).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amf0Value {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = Json::deserialize(d)?;
+        amf0_from_json(&json).map_err(D::Error::custom)
+    }
+}
+
+/// Converts a value to and from its JSON representation. Implemented for
+/// [`Amf0Value`] and [`Amf3Value`] so [`ValueJson`]/[`PairJson`] can be generic
+/// over either AMF version.
+trait JsonConvert: Sized {
+    fn to_json(&self) -> Json;
+    fn from_json(json: &Json) -> Result<Self, JsonError>;
+}
+
+impl JsonConvert for Amf0Value {
+    fn to_json(&self) -> Json {
+        amf0_to_json(self)
+    }
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        amf0_from_json(json)
+    }
+}
+
+impl JsonConvert for Amf3Value {
+    fn to_json(&self) -> Json {
+        amf3_to_json(self)
+    }
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        amf3_from_json(json)
+    }
+}
+
+/// A newtype around an `Amf0Value`/`Amf3Value` that the orphan rule lets us
+/// implement `serde` on, so it can be embedded as a field in your own derived
+/// types instead of only at the top level via [`SolVariant`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueJson<V>(pub V);
+
+impl<V: JsonConvert> Serialize for ValueJson<V> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.to_json().serialize(s)
+    }
+}
+
+impl<'de, V: JsonConvert> Deserialize<'de> for ValueJson<V> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = Json::deserialize(d)?;
+        V::from_json(&json).map(ValueJson).map_err(D::Error::custom)
+    }
+}
+
+/// A newtype around a [`Pair`], for the same reason as [`ValueJson`]. Uses the
+/// same `{ "key", "value" }` shape [`pairs_to_json`] gives a whole slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairJson<V>(pub Pair<V>);
+
+impl<V: JsonConvert> Serialize for PairJson<V> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        json!({ "key": self.0.key, "value": self.0.value.to_json() }).serialize(s)
+    }
+}
+
+impl<'de, V: JsonConvert> Deserialize<'de> for PairJson<V> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let json = Json::deserialize(d)?;
+        let key = as_str(field(&json, "key").map_err(D::Error::custom)?)
+            .map_err(D::Error::custom)?
+            .to_owned();
+        let value = V::from_json(field(&json, "value").map_err(D::Error::custom)?)
+            .map_err(D::Error::custom)?;
+        Ok(PairJson(Pair { key, value }))
+    }
+}
+
+fn sol_to_json(sol: &SolVariant) -> Json {
+    let (version, values) = match sol {
+        SolVariant::Amf0(sol) => (0, pairs_to_json(&sol.root_object, amf0_to_json)),
+        SolVariant::Amf3(sol) => (3, pairs_to_json(&sol.root_object, amf3_to_json)),
+    };
+    json!({
+        "root_name": sol.root_name(),
+        "amf_version": version,
+        "values": values,
+    })
+}
+
+fn sol_from_json(json: &Json) -> Result<SolVariant, JsonError> {
+    let root_name = json
+        .get("root_name")
+        .and_then(Json::as_str)
+        .ok_or(JsonError::MissingField("root_name"))?
+        .to_owned();
+    let version = json
+        .get("amf_version")
+        .and_then(Json::as_u64)
+        .ok_or(JsonError::MissingField("amf_version"))?;
+    let values = json
+        .get("values")
+        .and_then(Json::as_array)
+        .ok_or(JsonError::MissingField("values"))?;
+    match version {
+        0 => Ok(SolVariant::Amf0(Sol::new(
+            root_name,
+            pairs_from_json(values, amf0_from_json)?,
+        ))),
+        3 => Ok(SolVariant::Amf3(Sol::new(
+            root_name,
+            pairs_from_json(values, amf3_from_json)?,
+        ))),
+        other => Err(JsonError::UnknownAmfVersion(other)),
+    }
+}
+
+/// Pairs become a JSON *array* of `{ "key", "value" }` entries rather than a
+/// JSON object, so order is preserved and duplicate keys survive — a plain
+/// object would silently collapse both, breaking `sol -> json -> sol`.
+fn pairs_to_json<V>(pairs: &[Pair<V>], to_json: impl Fn(&V) -> Json) -> Json {
+    Json::Array(
+        pairs
+            .iter()
+            .map(|pair| json!({ "key": pair.key, "value": to_json(&pair.value) }))
+            .collect(),
+    )
+}
+
+fn pairs_from_json<V>(
+    items: &[Json],
+    from_json: impl Fn(&Json) -> Result<V, JsonError>,
+) -> Result<Vec<Pair<V>>, JsonError> {
+    items
+        .iter()
+        .map(|item| {
+            Ok(Pair {
+                key: as_str(field(item, "key")?)?.to_owned(),
+                value: from_json(field(item, "value")?)?,
+            })
+        })
+        .collect()
+}
+
+fn amf0_to_json(value: &Amf0Value) -> Json {
+    match value {
+        Amf0Value::Num(n) => double_to_json(*n),
+        Amf0Value::Bool(b) => json!(b),
+        Amf0Value::String(s) => json!(s),
+        Amf0Value::Object(entries) => pairs_to_json(entries, amf0_to_json),
+    }
+}
+
+fn amf0_from_json(json: &Json) -> Result<Amf0Value, JsonError> {
+    match json {
+        Json::Bool(b) => Ok(Amf0Value::Bool(*b)),
+        Json::Number(_) => Ok(Amf0Value::Num(json_to_double(json)?)),
+        Json::String(s) => Ok(Amf0Value::String(s.clone())),
+        Json::Array(items) => Ok(Amf0Value::Object(pairs_from_json(items, amf0_from_json)?)),
+        // The only bare object is the non-finite-number wrapper.
+        Json::Object(_) => Ok(Amf0Value::Num(json_to_double(json)?)),
+        _ => Err(JsonError::BadValue),
+    }
+}
+
+fn amf3_to_json(value: &Amf3Value) -> Json {
+    use Amf3Value as V;
+    match value {
+        V::Undefined => json!({ "$undefined": Json::Null }),
+        V::Null => Json::Null,
+        V::Boolean(b) => json!(b),
+        V::Integer(n) => json!({ "$int": n }),
+        V::Double(n) => double_to_json(*n),
+        V::String(s) => json!(s),
+        V::XmlDocument(s) => json!({ "$xmldoc": s }),
+        V::Xml(s) => json!({ "$xml": s }),
+        V::Date { unix_time } => json!({
+            "$date": { "secs": unix_time.as_secs(), "nanos": unix_time.subsec_nanos() }
+        }),
+        V::ByteArray(bytes) => json!({ "$bytes": hex::encode(bytes) }),
+        V::Array {
+            assoc_entries,
+            dense_entries,
+        } => {
+            let dense: Vec<Json> = dense_entries.iter().map(amf3_to_json).collect();
+            if assoc_entries.is_empty() {
+                Json::Array(dense)
+            } else {
+                json!({
+                    "$array": {
+                        "assoc": pairs_to_json(assoc_entries, amf3_to_json),
+                        "dense": dense,
+                    }
+                })
+            }
+        }
+        V::Object {
+            class_name,
+            sealed_count,
+            entries,
+        } => json!({
+            "$object": {
+                "class": class_name,
+                "sealed_count": sealed_count,
+                "entries": pairs_to_json(entries, amf3_to_json),
+            }
+        }),
+        V::IntVector { is_fixed, entries } => vector_to_json("int", *is_fixed, entries),
+        V::UintVector { is_fixed, entries } => vector_to_json("uint", *is_fixed, entries),
+        V::DoubleVector { is_fixed, entries } => {
+            // Route each element through `double_to_json` so non-finite values
+            // survive rather than collapsing to `null`.
+            let entries: Vec<Json> = entries.iter().map(|n| double_to_json(*n)).collect();
+            json!({ "$vector": { "type": "double", "fixed": is_fixed, "entries": entries } })
+        }
+        V::ObjectVector {
+            class_name,
+            is_fixed,
+            entries,
+        } => json!({
+            "$objectvector": {
+                "class": class_name,
+                "fixed": is_fixed,
+                "entries": entries.iter().map(amf3_to_json).collect::<Vec<_>>(),
+            }
+        }),
+        V::Dictionary { is_weak, entries } => json!({
+            "$dict": {
+                "weak": is_weak,
+                "entries": entries
+                    .iter()
+                    .map(|(k, v)| json!([amf3_to_json(k), amf3_to_json(v)]))
+                    .collect::<Vec<_>>(),
+            }
+        }),
+    }
+}
+
+fn vector_to_json<T: Serialize>(ty: &str, is_fixed: bool, entries: &[T]) -> Json {
+    json!({ "$vector": { "type": ty, "fixed": is_fixed, "entries": entries } })
+}
+
+fn amf3_from_json(json: &Json) -> Result<Amf3Value, JsonError> {
+    use Amf3Value as V;
+    match json {
+        Json::Null => Ok(V::Null),
+        Json::Bool(b) => Ok(V::Boolean(*b)),
+        Json::Number(_) => Ok(V::Double(json_to_double(json)?)),
+        Json::String(s) => Ok(V::String(s.clone())),
+        Json::Array(items) => Ok(V::Array {
+            assoc_entries: Vec::new(),
+            dense_entries: items.iter().map(amf3_from_json).collect::<Result<_, _>>()?,
+        }),
+        Json::Object(map) => amf3_tagged_from_json(map),
+    }
+}
+
+fn amf3_tagged_from_json(map: &Map<String, Json>) -> Result<Amf3Value, JsonError> {
+    use Amf3Value as V;
+    // Every object is written as a tagged wrapper (AMF objects themselves use
+    // `$object`), so a bare untagged object is malformed input.
+    let Some((tag, body)) = map.iter().next().filter(|_| map.len() == 1 && is_tag(map)) else {
+        return Err(JsonError::BadValue);
+    };
+    match tag.as_str() {
+        "$undefined" => Ok(V::Undefined),
+        "$int" => Ok(V::Integer(
+            body.as_i64().ok_or(JsonError::BadNumber)? as i32,
+        )),
+        "$double" => Ok(V::Double(parse_nonfinite(as_str(body)?)?)),
+        "$xmldoc" => Ok(V::XmlDocument(as_str(body)?.to_owned())),
+        "$xml" => Ok(V::Xml(as_str(body)?.to_owned())),
+        "$bytes" => Ok(V::ByteArray(
+            hex::decode(as_str(body)?).ok_or(JsonError::BadHex)?,
+        )),
+        "$date" => Ok(V::Date {
+            unix_time: Duration::new(
+                field(body, "secs")?.as_u64().ok_or(JsonError::BadNumber)?,
+                field(body, "nanos")?.as_u64().ok_or(JsonError::BadNumber)? as u32,
+            ),
+        }),
+        "$array" => Ok(V::Array {
+            assoc_entries: pairs_from_json(
+                field(body, "assoc")?
+                    .as_array()
+                    .ok_or(JsonError::BadValue)?,
+                amf3_from_json,
+            )?,
+            dense_entries: field(body, "dense")?
+                .as_array()
+                .ok_or(JsonError::BadValue)?
+                .iter()
+                .map(amf3_from_json)
+                .collect::<Result<_, _>>()?,
+        }),
+        "$object" => Ok(V::Object {
+            class_name: field(body, "class")?.as_str().map(str::to_owned),
+            sealed_count: field(body, "sealed_count")?
+                .as_u64()
+                .ok_or(JsonError::BadNumber)? as usize,
+            entries: pairs_from_json(
+                field(body, "entries")?
+                    .as_array()
+                    .ok_or(JsonError::BadValue)?,
+                amf3_from_json,
+            )?,
+        }),
+        "$vector" => vector_from_json(body),
+        "$objectvector" => Ok(V::ObjectVector {
+            class_name: field(body, "class")?.as_str().map(str::to_owned),
+            is_fixed: field(body, "fixed")?.as_bool().ok_or(JsonError::BadValue)?,
+            entries: field(body, "entries")?
+                .as_array()
+                .ok_or(JsonError::BadValue)?
+                .iter()
+                .map(amf3_from_json)
+                .collect::<Result<_, _>>()?,
+        }),
+        "$dict" => Ok(V::Dictionary {
+            is_weak: field(body, "weak")?.as_bool().ok_or(JsonError::BadValue)?,
+            entries: field(body, "entries")?
+                .as_array()
+                .ok_or(JsonError::BadValue)?
+                .iter()
+                .map(|pair| {
+                    let pair = pair.as_array().ok_or(JsonError::BadValue)?;
+                    match pair.as_slice() {
+                        [k, v] => Ok((amf3_from_json(k)?, amf3_from_json(v)?)),
+                        _ => Err(JsonError::BadValue),
+                    }
+                })
+                .collect::<Result<_, _>>()?,
+        }),
+        other => Err(JsonError::UnknownTag(other.to_owned())),
+    }
+}
+
+fn vector_from_json(body: &Json) -> Result<Amf3Value, JsonError> {
+    use Amf3Value as V;
+    let is_fixed = field(body, "fixed")?.as_bool().ok_or(JsonError::BadValue)?;
+    let entries = field(body, "entries")?
+        .as_array()
+        .ok_or(JsonError::BadValue)?;
+    match field(body, "type")?.as_str().ok_or(JsonError::BadValue)? {
+        "int" => Ok(V::IntVector {
+            is_fixed,
+            entries: entries
+                .iter()
+                .map(|v| Ok(v.as_i64().ok_or(JsonError::BadNumber)? as i32))
+                .collect::<Result<_, JsonError>>()?,
+        }),
+        "uint" => Ok(V::UintVector {
+            is_fixed,
+            entries: entries
+                .iter()
+                .map(|v| Ok(v.as_u64().ok_or(JsonError::BadNumber)? as u32))
+                .collect::<Result<_, JsonError>>()?,
+        }),
+        "double" => Ok(V::DoubleVector {
+            is_fixed,
+            entries: entries
+                .iter()
+                .map(json_to_double)
+                .collect::<Result<_, JsonError>>()?,
+        }),
+        other => Err(JsonError::UnknownTag(format!("$vector/{other}"))),
+    }
+}
+
+fn is_tag(map: &Map<String, Json>) -> bool {
+    map.keys().next().is_some_and(|k| k.starts_with('$'))
+}
+
+fn field<'a>(json: &'a Json, name: &'static str) -> Result<&'a Json, JsonError> {
+    json.get(name).ok_or(JsonError::MissingField(name))
+}
+
+fn as_str(json: &Json) -> Result<&str, JsonError> {
+    json.as_str().ok_or(JsonError::BadValue)
+}
+
+/// Encode a double, tagging the non-finite values serde_json cannot represent
+/// (it would otherwise emit `null` for `NaN`/`±inf`, losing them on the way
+/// back in).
+fn double_to_json(n: f64) -> Json {
+    if n.is_finite() {
+        json!(n)
+    } else {
+        json!({ "$double": nonfinite_str(n) })
+    }
+}
+
+fn nonfinite_str(n: f64) -> &'static str {
+    if n.is_nan() {
+        "nan"
+    } else if n > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    }
+}
+
+fn parse_nonfinite(s: &str) -> Result<f64, JsonError> {
+    match s {
+        "nan" => Ok(f64::NAN),
+        "inf" => Ok(f64::INFINITY),
+        "-inf" => Ok(f64::NEG_INFINITY),
+        _ => Err(JsonError::BadNumber),
+    }
+}
+
+/// Decode a double from either a plain JSON number or the `$double` wrapper
+/// that carries a non-finite value.
+fn json_to_double(json: &Json) -> Result<f64, JsonError> {
+    match json {
+        Json::Number(_) => json.as_f64().ok_or(JsonError::BadNumber),
+        Json::Object(map) if map.len() == 1 => parse_nonfinite(as_str(field(json, "$double")?)?),
+        _ => Err(JsonError::BadNumber),
+    }
+}
+
+/// Something went wrong mapping a `.sol` to or from JSON.
+#[derive(Debug)]
+pub enum JsonError {
+    MissingField(&'static str),
+    UnknownTag(String),
+    UnknownAmfVersion(u64),
+    BadNumber,
+    BadHex,
+    BadValue,
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::MissingField(name) => write!(f, "missing field: {name}"),
+            JsonError::UnknownTag(tag) => write!(f, "unknown tagged wrapper: {tag}"),
+            JsonError::UnknownAmfVersion(v) => write!(f, "unknown AMF version: {v}"),
+            JsonError::BadNumber => write!(f, "value out of range or not a number"),
+            JsonError::BadHex => write!(f, "invalid hex in $bytes"),
+            JsonError::BadValue => write!(f, "value did not match the expected shape"),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sol;
+
+    #[test]
+    fn sol_json_round_trips_losslessly() {
+        use Amf3Value as V;
+        let root = vec![
+            Pair {
+                key: "gold".to_owned(),
+                value: V::Integer(42),
+            },
+            // Non-finite doubles must survive rather than collapse to null.
+            Pair {
+                key: "ratio".to_owned(),
+                value: V::Double(f64::NAN),
+            },
+            // Duplicate keys must both survive and keep their order.
+            Pair {
+                key: "dup".to_owned(),
+                value: V::Boolean(true),
+            },
+            Pair {
+                key: "dup".to_owned(),
+                value: V::Boolean(false),
+            },
+            Pair {
+                key: "stats".to_owned(),
+                value: V::Object {
+                    class_name: Some("Stats".to_owned()),
+                    sealed_count: 1,
+                    entries: vec![Pair {
+                        key: "hp".to_owned(),
+                        value: V::Integer(100),
+                    }],
+                },
+            },
+            Pair {
+                key: "inv".to_owned(),
+                value: V::Array {
+                    assoc_entries: vec![Pair {
+                        key: "owner".to_owned(),
+                        value: V::String("hero".to_owned()),
+                    }],
+                    dense_entries: vec![V::Integer(1), V::Integer(2)],
+                },
+            },
+            Pair {
+                key: "when".to_owned(),
+                value: V::Date {
+                    unix_time: Duration::new(1_700_000_000, 500),
+                },
+            },
+            Pair {
+                key: "blob".to_owned(),
+                value: V::ByteArray(vec![0xde, 0xad, 0xbe, 0xef]),
+            },
+            Pair {
+                key: "speeds".to_owned(),
+                value: V::DoubleVector {
+                    is_fixed: false,
+                    entries: vec![1.5, f64::INFINITY],
+                },
+            },
+            Pair {
+                key: "bag".to_owned(),
+                value: V::Dictionary {
+                    is_weak: false,
+                    entries: vec![(V::String("k".to_owned()), V::Integer(7))],
+                },
+            },
+        ];
+        let sol = SolVariant::Amf3(Sol::new("save".to_owned(), root));
+        let json = sol_to_json(&sol);
+        let reparsed = sol_from_json(&json).unwrap();
+        // A second trip must produce byte-identical JSON: order, duplicate keys
+        // and non-finite numbers all preserved.
+        assert_eq!(json, sol_to_json(&reparsed));
+    }
+}
@@ -74,6 +74,16 @@ impl<'a> Display for Amf0ObjDisplay<'a> {
 
 pub type Amf3Value = amf::Amf3Value;
 
+pub mod hex;
+pub mod schema;
+pub mod selector;
+pub mod text;
+
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+pub use json::{read_from_json, JsonError, PairJson, ValueJson};
+
 /// AMF version 0
 #[derive(Debug)]
 pub enum Amf0 {}
@@ -183,7 +193,15 @@ fn write_value(value: &Amf0Value, w: &mut impl Write) -> io::Result<()> {
             w.write_u16::<BE>(s.len() as u16)?;
             w.write_all(s.as_bytes())?;
         }
-        Amf0Value::Object(_o) => todo!(),
+        Amf0Value::Object(o) => {
+            for pair in o {
+                write_key_and_type(pair, w)?;
+                write_value(&pair.value, w)?;
+            }
+            // Empty key followed by the object-end marker (type 9).
+            w.write_u16::<BE>(0)?;
+            w.write_u8(9)?;
+        }
     }
     Ok(())
     /*let value = match type_ {
@@ -255,32 +273,86 @@ impl SolVariant {
     }
 }
 
-pub fn read_from_file(path: &Path) -> Result<SolVariant, Box<dyn Error>> {
-    let data = std::fs::read(path).unwrap();
+/// An error encountered while reading a `.sol` file.
+///
+/// The read path used to `.unwrap()`/`panic!` its way through a malformed file,
+/// which aborts the whole process — fatal for a GUI editor. These variants let
+/// a caller surface the problem in a dialog instead.
+#[derive(Debug)]
+pub enum SolError {
+    /// The file did not start with the expected `00 BF` / `TCSO` magic.
+    BadMagic,
+    /// A value was tagged with an AMF type byte the reader does not know.
+    UnexpectedType(u8),
+    /// The stream ended in the middle of a header or value.
+    Truncated,
+    /// A key or string field was not valid UTF-8.
+    InvalidUtf8,
+    /// The AMF version byte was neither [`Amf0::ID`] nor [`Amf3::ID`].
+    UnknownVersion,
+    /// The file could not be opened or read (missing, permission denied, …).
+    Io(io::Error),
+    /// The AMF3 decoder rejected the stream.
+    Amf(Box<dyn Error>),
+}
+
+impl Display for SolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolError::BadMagic => write!(f, "not a .sol file (bad magic)"),
+            SolError::UnexpectedType(t) => write!(f, "unexpected AMF type byte: {t:02X}"),
+            SolError::Truncated => write!(f, "truncated stream"),
+            SolError::InvalidUtf8 => write!(f, "invalid UTF-8 in a string field"),
+            SolError::UnknownVersion => write!(f, "unknown AMF version"),
+            SolError::Io(e) => write!(f, "could not read file: {e}"),
+            SolError::Amf(e) => write!(f, "AMF decode error: {e}"),
+        }
+    }
+}
+
+impl Error for SolError {}
+
+impl From<io::Error> for SolError {
+    /// Used for reads over the already-loaded in-memory buffer, where the only
+    /// way a read fails is running off the end. Opening the file is handled
+    /// separately so a missing file reports [`SolError::Io`], not `Truncated`.
+    fn from(_: io::Error) -> Self {
+        SolError::Truncated
+    }
+}
+
+impl From<std::str::Utf8Error> for SolError {
+    fn from(_: std::str::Utf8Error) -> Self {
+        SolError::InvalidUtf8
+    }
+}
+
+pub fn read_from_file(path: &Path) -> Result<SolVariant, SolError> {
+    let data = std::fs::read(path).map_err(SolError::Io)?;
     let mut cursor = std::io::Cursor::new(data);
     let mut magic = [0; 2];
-    cursor.read_exact(&mut magic).unwrap();
-    assert!(magic == BF_MAGIC, "Unsupported format: {:X?}", magic);
-    let len = cursor.read_u32::<BE>().unwrap();
+    cursor.read_exact(&mut magic)?;
+    if magic != BF_MAGIC {
+        return Err(SolError::BadMagic);
+    }
+    let len = cursor.read_u32::<BE>()?;
     let mut type_ = [0; 4];
-    cursor.read_exact(&mut type_).unwrap();
-    assert!(type_ == TCSO_MAGIC);
+    cursor.read_exact(&mut type_)?;
+    if type_ != TCSO_MAGIC {
+        return Err(SolError::BadMagic);
+    }
     let mut tail = [0; 6];
-    cursor.read_exact(&mut tail).unwrap();
-    assert!(tail == TAIL_MAGIC);
-    let root_name_len = cursor.read_u16::<BE>().unwrap();
+    cursor.read_exact(&mut tail)?;
+    if tail != TAIL_MAGIC {
+        return Err(SolError::BadMagic);
+    }
+    let root_name_len = cursor.read_u16::<BE>()?;
     let mut root_name = vec![0; root_name_len as usize];
-    cursor.read_exact(&mut root_name).unwrap();
-    let root_name = std::str::from_utf8(&root_name).unwrap().to_owned();
+    cursor.read_exact(&mut root_name)?;
+    let root_name = std::str::from_utf8(&root_name)?.to_owned();
     let mut blob = [0; 4];
-    assert_eq!(blob[0], 0);
-    assert_eq!(blob[1], 0);
-    assert_eq!(blob[2], 0);
-    cursor.read_exact(&mut blob).unwrap();
-    let amf_ver = match amf_ver_spec(blob) {
-        Some(ver) => ver,
-        None => panic!("Unknown AMF version"),
-    };
+    cursor.read_exact(&mut blob)?;
+    let amf_ver = amf_ver_spec(blob).ok_or(SolError::UnknownVersion)?;
     match amf_ver {
         AmfVerSpec::Amf0 => Ok(SolVariant::Amf0(Sol {
             len,
@@ -315,73 +387,139 @@ fn amf_ver_spec(blob: [u8; 4]) -> Option<AmfVerSpec> {
 fn read_amf0(
     mut cursor: std::io::Cursor<Vec<u8>>,
     len: u64,
-) -> Result<Vec<Pair<Amf0Value>>, Box<dyn Error>> {
+) -> Result<Vec<Pair<Amf0Value>>, SolError> {
     let mut kvpairs = Vec::new();
     loop {
         if cursor.position() - 6 == len {
             return Ok(kvpairs);
         }
-        let (key, type_) = read_key_and_type(&mut cursor);
-        let value = read_value(type_, &mut cursor);
+        let (key, type_) = read_key_and_type(&mut cursor)?;
+        let value = read_value(type_, &mut cursor)?;
         kvpairs.push(Pair { key, value });
-        let _padding = cursor.read_u8().unwrap();
+        let _padding = cursor.read_u8()?;
     }
 }
 
-fn read_value(type_: u8, cursor: &mut std::io::Cursor<Vec<u8>>) -> Amf0Value {
+fn read_value(type_: u8, cursor: &mut std::io::Cursor<Vec<u8>>) -> Result<Amf0Value, SolError> {
     let value = match type_ {
         Amf0Value::NUM => {
-            let num = cursor.read_f64::<BE>().unwrap();
+            let num = cursor.read_f64::<BE>()?;
             Amf0Value::Num(num)
         }
         Amf0Value::BOOL => {
-            let bool_marker = cursor.read_u8().unwrap();
+            let bool_marker = cursor.read_u8()?;
             Amf0Value::Bool(bool_marker != 0)
         }
         Amf0Value::STRING => {
-            let len = cursor.read_u16::<BE>().unwrap();
+            let len = cursor.read_u16::<BE>()?;
             let mut buf = vec![0; len as usize];
-            cursor.read_exact(&mut buf).unwrap();
-            Amf0Value::String(std::str::from_utf8(&buf).unwrap().to_owned())
+            cursor.read_exact(&mut buf)?;
+            Amf0Value::String(std::str::from_utf8(&buf)?.to_owned())
         }
         Amf0Value::OBJECT => {
             let mut kvpairs = Vec::new();
             loop {
-                let (key, type_) = read_key_and_type(cursor);
+                let (key, type_) = read_key_and_type(cursor)?;
                 if type_ == 9 {
-                    return Amf0Value::Object(kvpairs);
+                    return Ok(Amf0Value::Object(kvpairs));
                 }
-                let value = read_value(type_, cursor);
+                let value = read_value(type_, cursor)?;
                 kvpairs.push(Pair { key, value });
             }
         }
-        _ => panic!("Unexpected type: {:02X}", type_),
+        _ => return Err(SolError::UnexpectedType(type_)),
     };
-    value
+    Ok(value)
 }
 
-fn read_key_and_type(cursor: &mut std::io::Cursor<Vec<u8>>) -> (String, u8) {
-    let key_len = cursor.read_u16::<BE>().unwrap();
+fn read_key_and_type(cursor: &mut std::io::Cursor<Vec<u8>>) -> Result<(String, u8), SolError> {
+    let key_len = cursor.read_u16::<BE>()?;
     let mut key = vec![0; key_len as usize];
-    cursor.read_exact(&mut key).unwrap();
-    let key = std::str::from_utf8(&key).unwrap().to_owned();
-    let type_ = cursor.read_u8().unwrap();
-    (key, type_)
+    cursor.read_exact(&mut key)?;
+    let key = std::str::from_utf8(&key)?.to_owned();
+    let type_ = cursor.read_u8()?;
+    Ok((key, type_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn amf0_nested_object_round_trips() {
+        let inner = vec![
+            Pair {
+                key: "hp".to_owned(),
+                value: Amf0Value::Num(100.0),
+            },
+            Pair {
+                key: "name".to_owned(),
+                value: Amf0Value::String("hero".to_owned()),
+            },
+        ];
+        let root = vec![
+            Pair {
+                key: "player".to_owned(),
+                value: Amf0Value::Object(inner),
+            },
+            Pair {
+                key: "level".to_owned(),
+                value: Amf0Value::Num(3.0),
+            },
+        ];
+        let sol = Sol::<Amf0>::new("save".to_owned(), root);
+        let mut first = Cursor::new(Vec::new());
+        sol.write(&mut first).unwrap();
+        let bytes = first.into_inner();
+
+        // Round-trip through the read path and back out again.
+        let path = std::env::temp_dir().join("soledit_amf0_nested_round_trip.sol");
+        std::fs::write(&path, &bytes).unwrap();
+        let reparsed = read_from_file(&path).unwrap();
+        let mut second = Cursor::new(Vec::new());
+        match reparsed {
+            SolVariant::Amf0(sol) => sol.write(&mut second).unwrap(),
+            SolVariant::Amf3(_) => panic!("expected an AMF0 sol"),
+        }
+        assert_eq!(bytes, second.into_inner());
+    }
+
+    #[test]
+    fn truncated_file_is_an_error_not_a_panic() {
+        // A well-formed header and key followed by a number value that is cut
+        // off mid-way: the reader should report `Truncated` rather than unwinding.
+        let sol = Sol::<Amf0>::new(
+            "save".to_owned(),
+            vec![Pair {
+                key: "hp".to_owned(),
+                value: Amf0Value::Num(100.0),
+            }],
+        );
+        let mut buf = Cursor::new(Vec::new());
+        sol.write(&mut buf).unwrap();
+        let mut bytes = buf.into_inner();
+        bytes.truncate(bytes.len() - 4);
+
+        let path = std::env::temp_dir().join("soledit_truncated.sol");
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(read_from_file(&path), Err(SolError::Truncated)));
+    }
 }
 
 fn read_amf3(
     cursor: std::io::Cursor<Vec<u8>>,
     len: u64,
-) -> Result<Vec<Pair<amf::Amf3Value>>, Box<dyn Error>> {
+) -> Result<Vec<Pair<amf::Amf3Value>>, SolError> {
     let mut kvpairs = Vec::new();
     let mut decoder = amf::amf3::Decoder::new(cursor);
     loop {
         if decoder.inner().position() - 6 == len {
             return Ok(kvpairs);
         }
-        let key = decoder.decode_utf8().unwrap();
-        let value = decoder.decode().unwrap();
-        let _padding = decoder.inner_mut().read_u8().unwrap();
+        let key = decoder.decode_utf8().map_err(|e| SolError::Amf(Box::new(e)))?;
+        let value = decoder.decode().map_err(|e| SolError::Amf(Box::new(e)))?;
+        let _padding = decoder.inner_mut().read_u8()?;
         kvpairs.push(Pair { key, value });
     }
 }
@@ -0,0 +1,391 @@
+//! A declarative schema for validating the shape of a loaded `.sol`.
+//!
+//! A modder describes the save file they expect — which root keys must be
+//! present, the AMF type of each, the fields of nested objects, the element
+//! type of an array, the permitted range of a number — as a small [`Schema`]
+//! value. [`validate`] then checks a [`SolVariant`] against it and reports every
+//! mismatch, so the editor can flag corrupt or out-of-range edits before writing
+//! them back.
+//!
+//! Each [`SchemaError`] carries the [`Path`] of the offending value, reusing the
+//! same path representation the [`selector`](crate::selector) module produces for
+//! matches.
+
+use crate::selector::{Path, Segment};
+use crate::{Amf0Value, Amf3Value, Pair, SolVariant};
+
+/// The expected shape of a single value.
+#[derive(Debug, Clone)]
+pub enum Shape {
+    /// Any value is acceptable.
+    Any,
+    /// A boolean.
+    Bool,
+    /// A string.
+    Str,
+    /// A number, optionally constrained to an inclusive `[min, max]` range.
+    Num { min: Option<f64>, max: Option<f64> },
+    /// An object carrying at least the listed fields. Extra fields are allowed.
+    Object(Vec<Field>),
+    /// A dense array whose every element matches the given shape.
+    Array(Box<Shape>),
+}
+
+impl Shape {
+    /// An unconstrained number.
+    pub fn num() -> Shape {
+        Shape::Num {
+            min: None,
+            max: None,
+        }
+    }
+    /// A number constrained to an inclusive range.
+    pub fn range(min: f64, max: f64) -> Shape {
+        Shape::Num {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+    /// The name used for this shape in a [`Mismatch::Type`] report.
+    fn name(&self) -> &'static str {
+        match self {
+            Shape::Any => "any",
+            Shape::Bool => "bool",
+            Shape::Str => "string",
+            Shape::Num { .. } => "number",
+            Shape::Object(_) => "object",
+            Shape::Array(_) => "array",
+        }
+    }
+}
+
+/// A required field of an object, by key.
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub key: String,
+    pub shape: Shape,
+}
+
+impl Field {
+    pub fn new(key: impl Into<String>, shape: Shape) -> Field {
+        Field {
+            key: key.into(),
+            shape,
+        }
+    }
+}
+
+/// The expected shape of a whole `.sol`: the required root keys and their shapes.
+#[derive(Debug, Clone, Default)]
+pub struct Schema(pub Vec<Field>);
+
+/// How a value failed to match its schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mismatch {
+    /// A required key was absent from an object.
+    MissingKey(String),
+    /// The value had the wrong AMF type.
+    Type {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// A number fell outside its permitted range.
+    OutOfRange {
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+/// A single schema violation, located by [`Path`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    pub path: Path,
+    pub mismatch: Mismatch,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}: ", self.path)?;
+        match &self.mismatch {
+            Mismatch::MissingKey(key) => write!(f, "missing required key {key:?}"),
+            Mismatch::Type { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            Mismatch::OutOfRange { value, min, max } => match (min, max) {
+                (Some(min), Some(max)) => write!(f, "{value} is outside {min}..={max}"),
+                (Some(min), None) => write!(f, "{value} is below {min}"),
+                (None, Some(max)) => write!(f, "{value} is above {max}"),
+                (None, None) => write!(f, "{value} is out of range"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Check a loaded `.sol` against `schema`, collecting every violation.
+pub fn validate(sol: &SolVariant, schema: &Schema) -> Result<(), Vec<SchemaError>> {
+    let mut errors = Vec::new();
+    let root = Path::default();
+    match sol {
+        SolVariant::Amf0(sol) => check_object(&sol.root_object, &schema.0, &root, &mut errors),
+        SolVariant::Amf3(sol) => check_object(&sol.root_object, &schema.0, &root, &mut errors),
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// A value the schema checker can introspect, abstracting over the AMF version.
+trait SchemaValue: Sized {
+    /// The name used for this value's type in a [`Mismatch::Type`] report.
+    fn type_name(&self) -> &'static str;
+    fn as_number(&self) -> Option<f64>;
+    fn is_bool(&self) -> bool;
+    fn is_string(&self) -> bool;
+    fn object_fields(&self) -> Option<&[Pair<Self>]>;
+    fn array_elems(&self) -> Option<&[Self]>;
+}
+
+fn check<V: SchemaValue>(value: &V, shape: &Shape, path: &Path, errors: &mut Vec<SchemaError>) {
+    match shape {
+        Shape::Any => {}
+        Shape::Bool => {
+            if !value.is_bool() {
+                errors.push(type_error(shape, value, path));
+            }
+        }
+        Shape::Str => {
+            if !value.is_string() {
+                errors.push(type_error(shape, value, path));
+            }
+        }
+        Shape::Num { min, max } => match value.as_number() {
+            Some(n) => {
+                let below = min.is_some_and(|min| n < min);
+                let above = max.is_some_and(|max| n > max);
+                if below || above {
+                    errors.push(SchemaError {
+                        path: path.clone(),
+                        mismatch: Mismatch::OutOfRange {
+                            value: n,
+                            min: *min,
+                            max: *max,
+                        },
+                    });
+                }
+            }
+            None => errors.push(type_error(shape, value, path)),
+        },
+        Shape::Object(fields) => match value.object_fields() {
+            Some(entries) => check_object(entries, fields, path, errors),
+            None => errors.push(type_error(shape, value, path)),
+        },
+        Shape::Array(elem) => match value.array_elems() {
+            Some(elems) => {
+                for (i, e) in elems.iter().enumerate() {
+                    check(e, elem, &pushed(path, Segment::Index(i)), errors);
+                }
+            }
+            None => errors.push(type_error(shape, value, path)),
+        },
+    }
+}
+
+fn check_object<V: SchemaValue>(
+    entries: &[Pair<V>],
+    fields: &[Field],
+    path: &Path,
+    errors: &mut Vec<SchemaError>,
+) {
+    for field in fields {
+        let child_path = pushed(path, Segment::Key(field.key.clone()));
+        match entries.iter().find(|p| p.key == field.key) {
+            Some(pair) => check(&pair.value, &field.shape, &child_path, errors),
+            None => errors.push(SchemaError {
+                path: child_path,
+                mismatch: Mismatch::MissingKey(field.key.clone()),
+            }),
+        }
+    }
+}
+
+fn type_error<V: SchemaValue>(shape: &Shape, value: &V, path: &Path) -> SchemaError {
+    SchemaError {
+        path: path.clone(),
+        mismatch: Mismatch::Type {
+            expected: shape.name(),
+            found: value.type_name(),
+        },
+    }
+}
+
+fn pushed(path: &Path, seg: Segment) -> Path {
+    let mut path = path.clone();
+    path.0.push(seg);
+    path
+}
+
+impl SchemaValue for Amf0Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Amf0Value::Num(_) => "number",
+            Amf0Value::Bool(_) => "bool",
+            Amf0Value::String(_) => "string",
+            Amf0Value::Object(_) => "object",
+        }
+    }
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Amf0Value::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+    fn is_bool(&self) -> bool {
+        matches!(self, Amf0Value::Bool(_))
+    }
+    fn is_string(&self) -> bool {
+        matches!(self, Amf0Value::String(_))
+    }
+    fn object_fields(&self) -> Option<&[Pair<Self>]> {
+        match self {
+            Amf0Value::Object(entries) => Some(entries.as_slice()),
+            _ => None,
+        }
+    }
+    fn array_elems(&self) -> Option<&[Self]> {
+        None
+    }
+}
+
+impl SchemaValue for Amf3Value {
+    fn type_name(&self) -> &'static str {
+        use Amf3Value as V;
+        match self {
+            V::Undefined => "undefined",
+            V::Null => "null",
+            V::Boolean(_) => "bool",
+            V::Integer(_) | V::Double(_) => "number",
+            V::String(_) => "string",
+            V::XmlDocument(_) | V::Xml(_) => "xml",
+            V::Date { .. } => "date",
+            V::ByteArray(_) => "bytearray",
+            V::Object { .. } => "object",
+            V::Array { .. } => "array",
+            V::IntVector { .. } | V::UintVector { .. } | V::DoubleVector { .. } => "vector",
+            V::ObjectVector { .. } => "vector",
+            V::Dictionary { .. } => "dictionary",
+        }
+    }
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Amf3Value::Integer(n) => Some(*n as f64),
+            Amf3Value::Double(n) => Some(*n),
+            _ => None,
+        }
+    }
+    fn is_bool(&self) -> bool {
+        matches!(self, Amf3Value::Boolean(_))
+    }
+    fn is_string(&self) -> bool {
+        matches!(self, Amf3Value::String(_))
+    }
+    fn object_fields(&self) -> Option<&[Pair<Self>]> {
+        match self {
+            Amf3Value::Object { entries, .. } => Some(entries.as_slice()),
+            _ => None,
+        }
+    }
+    fn array_elems(&self) -> Option<&[Self]> {
+        match self {
+            Amf3Value::Array { dense_entries, .. } => Some(dense_entries.as_slice()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sol;
+
+    #[test]
+    fn validate_collects_every_kind_of_violation() {
+        use Amf3Value as V;
+        let root = vec![
+            Pair {
+                key: "name".to_owned(),
+                value: V::Integer(1), // wrong type: schema wants a string
+            },
+            Pair {
+                key: "stats".to_owned(),
+                value: V::Object {
+                    class_name: None,
+                    sealed_count: 0,
+                    entries: vec![Pair {
+                        key: "hp".to_owned(),
+                        value: V::Integer(999), // out of range: schema caps at 100
+                    }],
+                },
+            },
+            Pair {
+                key: "inv".to_owned(),
+                value: V::Array {
+                    assoc_entries: Vec::new(),
+                    dense_entries: vec![V::Integer(1), V::Boolean(true)], // wrong elem type
+                },
+            },
+            // "level" is required by the schema but absent here.
+        ];
+        let sol = SolVariant::Amf3(Sol::new("save".to_owned(), root));
+        let schema = Schema(vec![
+            Field::new("name", Shape::Str),
+            Field::new(
+                "stats",
+                Shape::Object(vec![Field::new("hp", Shape::range(0.0, 100.0))]),
+            ),
+            Field::new("inv", Shape::Array(Box::new(Shape::num()))),
+            Field::new("level", Shape::num()),
+        ]);
+
+        let errors = validate(&sol, &schema).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                SchemaError {
+                    path: Path(vec![Segment::Key("name".to_owned())]),
+                    mismatch: Mismatch::Type {
+                        expected: "string",
+                        found: "number",
+                    },
+                },
+                SchemaError {
+                    path: Path(vec![
+                        Segment::Key("stats".to_owned()),
+                        Segment::Key("hp".to_owned()),
+                    ]),
+                    mismatch: Mismatch::OutOfRange {
+                        value: 999.0,
+                        min: Some(0.0),
+                        max: Some(100.0),
+                    },
+                },
+                SchemaError {
+                    path: Path(vec![Segment::Key("inv".to_owned()), Segment::Index(1)]),
+                    mismatch: Mismatch::Type {
+                        expected: "number",
+                        found: "bool",
+                    },
+                },
+                SchemaError {
+                    path: Path(vec![Segment::Key("level".to_owned())]),
+                    mismatch: Mismatch::MissingKey("level".to_owned()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! A small path-query selector language for reaching into nested values.
+//!
+//! The GUI used to filter pairs with `key.contains(…)`, which can never reach
+//! past a top-level key. A selector instead describes a walk through the tree:
+//!
+//! | step        | meaning                                             |
+//! |-------------|-----------------------------------------------------|
+//! | `.key`      | field access by name                                |
+//! | `[n]`       | dense-array index                                   |
+//! | `*`         | wildcard over one level                             |
+//! | `**`        | recursive descent                                   |
+//! | `[key=val]` | keep objects whose child `key` stringifies to `val` |
+//!
+//! So `player.**.gold` reaches every `gold` leaf anywhere beneath `player`. An
+//! empty selector matches every top-level pair, preserving the old behavior.
+//!
+//! Evaluation walks into [`Amf3Value::Object`] entries,
+//! [`Amf3Value::Array`] dense/assoc entries and [`Amf3Value::Dictionary`]
+//! entries (and [`Amf0Value::Object`] for AMF0), yielding each matching leaf as
+//! a `(Path, &mut Value)` via [`for_each_match`]. When a selector without `**`
+//! ends on a root pair (no descent into children was needed), the callback
+//! also gets `Some(&mut pair.key)` so the GUI can keep top-level keys editable.
+//! Matches reached through `**` get `None`, even a root pair's own value
+//! matched at zero depth.
+
+use crate::{Amf0Value, Amf3Value, Pair};
+
+/// A single segment of a concrete path to a matched value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Segment {
+    fn key(&self) -> Option<&str> {
+        match self {
+            Segment::Key(k) => Some(k),
+            Segment::Index(_) => None,
+        }
+    }
+    fn index(&self) -> Option<usize> {
+        match self {
+            Segment::Index(n) => Some(*n),
+            Segment::Key(_) => None,
+        }
+    }
+}
+
+/// A concrete location within a `.sol`, as produced by a match. Reused by the
+/// schema subsystem to point at the offending value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path(pub Vec<Segment>);
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for seg in &self.0 {
+            match seg {
+                Segment::Key(k) => write!(f, ".{k}")?,
+                Segment::Index(n) => write!(f, "[{n}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One step of a compiled selector.
+#[derive(Debug, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    Wildcard,
+    Recursive,
+    Predicate { key: String, value: String },
+}
+
+/// A compiled selector, ready to evaluate against a root object.
+#[derive(Debug)]
+pub struct Selector(Vec<Step>);
+
+impl Selector {
+    /// Compile a selector from its textual form.
+    pub fn parse(src: &str) -> Result<Selector, SelectorError> {
+        let mut steps = Vec::new();
+        let mut chars = src.chars().peekable();
+        while let Some(&ch) = chars.peek() {
+            match ch {
+                '.' => {
+                    chars.next();
+                }
+                '*' => {
+                    chars.next();
+                    if chars.peek() == Some(&'*') {
+                        chars.next();
+                        steps.push(Step::Recursive);
+                    } else {
+                        steps.push(Step::Wildcard);
+                    }
+                }
+                '[' => {
+                    chars.next();
+                    let mut body = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => body.push(c),
+                            None => return Err(SelectorError::UnterminatedBracket),
+                        }
+                    }
+                    steps.push(parse_bracket(&body)?);
+                }
+                _ => {
+                    let mut name = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c == '.' || c == '[' || c == '*' {
+                            break;
+                        }
+                        name.push(c);
+                        chars.next();
+                    }
+                    steps.push(Step::Key(name));
+                }
+            }
+        }
+        Ok(Selector(steps))
+    }
+}
+
+fn parse_bracket(body: &str) -> Result<Step, SelectorError> {
+    if let Some((key, value)) = body.split_once('=') {
+        Ok(Step::Predicate {
+            key: key.trim().to_owned(),
+            value: unquote(value.trim()).to_owned(),
+        })
+    } else {
+        body.trim()
+            .parse()
+            .map(Step::Index)
+            .map_err(|_| SelectorError::BadIndex(body.to_owned()))
+    }
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s)
+}
+
+/// A value that a selector can descend into.
+pub trait QueryValue: Sized {
+    /// Visit each immediate child together with the segment that reaches it.
+    fn for_each_child(&mut self, f: &mut dyn FnMut(Segment, &mut Self));
+    /// Look up an immediate child by key (for predicate evaluation).
+    fn child_by_key(&self, key: &str) -> Option<&Self>;
+    /// Stringified form compared against a predicate's right-hand side.
+    fn as_query_string(&self) -> String;
+}
+
+/// Evaluate `selector` against the root pairs, invoking `f` for every
+/// matching leaf with its concrete path, the owning key when the match is a
+/// root pair itself (`None` once the walk has descended into a child, and for
+/// every match reached through `**`, even at zero depth), and a mutable
+/// reference to the value.
+pub fn for_each_match<V: QueryValue>(
+    root: &mut [Pair<V>],
+    selector: &Selector,
+    f: &mut dyn FnMut(&Path, Option<&mut String>, &mut V),
+) {
+    match selector.0.split_first() {
+        None => {
+            for pair in root.iter_mut() {
+                let path = Path(vec![Segment::Key(pair.key.clone())]);
+                f(&path, Some(&mut pair.key), &mut pair.value);
+            }
+        }
+        Some((step, rest)) => match step {
+            Step::Key(k) => {
+                for pair in root.iter_mut().filter(|p| &p.key == k) {
+                    let path = Path(vec![Segment::Key(pair.key.clone())]);
+                    root_or_descend(rest, &path, pair, f);
+                }
+            }
+            Step::Index(n) => {
+                if let Some(pair) = root.get_mut(*n) {
+                    let path = Path(vec![Segment::Index(*n)]);
+                    root_or_descend(rest, &path, pair, f);
+                }
+            }
+            Step::Wildcard => {
+                for pair in root.iter_mut() {
+                    let path = Path(vec![Segment::Key(pair.key.clone())]);
+                    root_or_descend(rest, &path, pair, f);
+                }
+            }
+            Step::Recursive => {
+                for pair in root.iter_mut() {
+                    let path = Path(vec![Segment::Key(pair.key.clone())]);
+                    apply(&selector.0, &path, &mut pair.value, &mut |p, v| f(p, None, v));
+                }
+            }
+            Step::Predicate { key, value } => {
+                for pair in root.iter_mut() {
+                    if matches_predicate(&pair.value, key, value) {
+                        let path = Path(vec![Segment::Key(pair.key.clone())]);
+                        root_or_descend(rest, &path, pair, f);
+                    }
+                }
+            }
+        },
+    }
+}
+
+/// Shared tail of the root-level match arms: if `rest` is empty the selector
+/// is satisfied by the root pair itself, so hand back its key too; otherwise
+/// descend into its value and lose key access (children aren't `Pair`s).
+fn root_or_descend<V: QueryValue>(
+    rest: &[Step],
+    path: &Path,
+    pair: &mut Pair<V>,
+    f: &mut dyn FnMut(&Path, Option<&mut String>, &mut V),
+) {
+    if rest.is_empty() {
+        f(path, Some(&mut pair.key), &mut pair.value);
+    } else {
+        apply(rest, path, &mut pair.value, &mut |p, v| f(p, None, v));
+    }
+}
+
+fn apply<V: QueryValue>(
+    steps: &[Step],
+    path: &Path,
+    node: &mut V,
+    f: &mut dyn FnMut(&Path, &mut V),
+) {
+    let Some((step, rest)) = steps.split_first() else {
+        f(path, node);
+        return;
+    };
+    match step {
+        Step::Key(k) => node.for_each_child(&mut |seg, child| {
+            if seg.key() == Some(k.as_str()) {
+                apply(rest, &pushed(path, seg), child, &mut *f);
+            }
+        }),
+        Step::Index(n) => node.for_each_child(&mut |seg, child| {
+            if seg.index() == Some(*n) {
+                apply(rest, &pushed(path, seg), child, &mut *f);
+            }
+        }),
+        Step::Wildcard => node.for_each_child(&mut |seg, child| {
+            apply(rest, &pushed(path, seg), child, &mut *f);
+        }),
+        Step::Recursive => {
+            apply(rest, path, node, f); // `**` also matches zero levels
+            node.for_each_child(&mut |seg, child| {
+                apply(steps, &pushed(path, seg), child, &mut *f);
+            });
+        }
+        Step::Predicate { key, value } => {
+            if matches_predicate(node, key, value) {
+                apply(rest, path, node, f);
+            }
+        }
+    }
+}
+
+fn matches_predicate<V: QueryValue>(node: &V, key: &str, value: &str) -> bool {
+    node.child_by_key(key)
+        .is_some_and(|child| child.as_query_string() == value)
+}
+
+fn pushed(path: &Path, seg: Segment) -> Path {
+    let mut path = path.clone();
+    path.0.push(seg);
+    path
+}
+
+impl QueryValue for Amf0Value {
+    fn for_each_child(&mut self, f: &mut dyn FnMut(Segment, &mut Self)) {
+        if let Amf0Value::Object(entries) = self {
+            for pair in entries.iter_mut() {
+                f(Segment::Key(pair.key.clone()), &mut pair.value);
+            }
+        }
+    }
+    fn child_by_key(&self, key: &str) -> Option<&Self> {
+        match self {
+            Amf0Value::Object(entries) => {
+                entries.iter().find(|p| p.key == key).map(|p| &p.value)
+            }
+            _ => None,
+        }
+    }
+    fn as_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl QueryValue for Amf3Value {
+    fn for_each_child(&mut self, f: &mut dyn FnMut(Segment, &mut Self)) {
+        use Amf3Value as V;
+        match self {
+            V::Object { entries, .. } => {
+                for pair in entries.iter_mut() {
+                    f(Segment::Key(pair.key.clone()), &mut pair.value);
+                }
+            }
+            V::Array {
+                assoc_entries,
+                dense_entries,
+            } => {
+                for pair in assoc_entries.iter_mut() {
+                    f(Segment::Key(pair.key.clone()), &mut pair.value);
+                }
+                for (i, value) in dense_entries.iter_mut().enumerate() {
+                    f(Segment::Index(i), value);
+                }
+            }
+            V::Dictionary { entries, .. } => {
+                for (key, value) in entries.iter_mut() {
+                    f(Segment::Key(key.as_query_string()), value);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn child_by_key(&self, key: &str) -> Option<&Self> {
+        use Amf3Value as V;
+        match self {
+            V::Object { entries, .. } => {
+                entries.iter().find(|p| p.key == key).map(|p| &p.value)
+            }
+            V::Array { assoc_entries, .. } => assoc_entries
+                .iter()
+                .find(|p| p.key == key)
+                .map(|p| &p.value),
+            V::Dictionary { entries, .. } => entries
+                .iter()
+                .find(|(k, _)| k.as_query_string() == key)
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+    fn as_query_string(&self) -> String {
+        use Amf3Value as V;
+        match self {
+            V::String(s) => s.clone(),
+            V::Integer(n) => n.to_string(),
+            V::Double(n) => n.to_string(),
+            V::Boolean(b) => b.to_string(),
+            V::Null => "null".to_owned(),
+            V::Undefined => "undefined".to_owned(),
+            other => format!("{other:?}"),
+        }
+    }
+}
+
+/// Reasons a selector failed to compile.
+#[derive(Debug, PartialEq)]
+pub enum SelectorError {
+    UnterminatedBracket,
+    BadIndex(String),
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::UnterminatedBracket => write!(f, "unterminated `[` in selector"),
+            SelectorError::BadIndex(s) => write!(f, "invalid array index: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
@@ -0,0 +1,775 @@
+//! A canonical, round-trippable textual representation of a whole `.sol`.
+//!
+//! This module pairs a [`writer`](to_text) with a [`parser`](from_text):
+//! `text -> value -> text` and `sol -> text -> sol` both reproduce the input.
+//! Every AMF variant has a spelling, including `ByteArray` as hex, `Date`, and
+//! the vector/dictionary types.
+//!
+//! The grammar is deliberately small:
+//!
+//! ```text
+//! sol "root name" amf3 {
+//!     "key" = value
+//!     ...
+//! }
+//! ```
+//!
+//! where a value is one of `null`, `undefined`, `true`/`false`, an integer, a
+//! double (written with a decimal point or exponent, or as `nan`/`inf`/`-inf`,
+//! so it is never mistaken for an integer), a `"string"`, an `object`, an `[array]`,
+//! `bytes(hex)`, `date(secs, nanos)`, `xml("…")`/`xmldoc("…")`, a typed vector
+//! (`ivec`/`uvec`/`dvec`), an object vector (`ovec`), or a `dict`.
+
+use crate::{hex, Amf0, Amf0Value, Amf3Value, Pair, Sol, SolVariant};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Render a whole `.sol` as canonical text.
+pub fn to_text(sol: &SolVariant) -> String {
+    let mut w = Writer {
+        out: String::new(),
+        indent: 0,
+    };
+    w.write_sol(sol);
+    w.out
+}
+
+/// Parse canonical text back into a [`SolVariant`].
+pub fn from_text(src: &str) -> Result<SolVariant, TextError> {
+    let tokens = lex(src)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let sol = parser.parse_sol()?;
+    parser.expect_end()?;
+    Ok(sol)
+}
+
+// --- Writer ----------------------------------------------------------------
+
+struct Writer {
+    out: String,
+    indent: u32,
+}
+
+impl Writer {
+    fn line(&mut self, args: std::fmt::Arguments) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        let _ = self.out.write_fmt(args);
+        self.out.push('\n');
+    }
+
+    fn write_sol(&mut self, sol: &SolVariant) {
+        match sol {
+            SolVariant::Amf0(sol) => {
+                self.line(format_args!("sol {} amf0 {{", quote(&sol.root_name)));
+                self.indent += 1;
+                for pair in &sol.root_object {
+                    let value = self.amf0_value(&pair.value);
+                    self.line(format_args!("{} = {}", quote(&pair.key), value));
+                }
+                self.indent -= 1;
+                self.line(format_args!("}}"));
+            }
+            SolVariant::Amf3(sol) => {
+                self.line(format_args!("sol {} amf3 {{", quote(&sol.root_name)));
+                self.indent += 1;
+                for pair in &sol.root_object {
+                    let value = self.amf3_value(&pair.value);
+                    self.line(format_args!("{} = {}", quote(&pair.key), value));
+                }
+                self.indent -= 1;
+                self.line(format_args!("}}"));
+            }
+        }
+    }
+
+    fn amf0_value(&self, value: &Amf0Value) -> String {
+        match value {
+            Amf0Value::Num(n) => double(*n),
+            Amf0Value::Bool(b) => b.to_string(),
+            Amf0Value::String(s) => quote(s),
+            Amf0Value::Object(entries) => {
+                let mut s = String::from("object { ");
+                for pair in entries {
+                    s.push_str(&quote(&pair.key));
+                    s.push_str(" = ");
+                    s.push_str(&self.amf0_value(&pair.value));
+                    s.push_str(", ");
+                }
+                s.push('}');
+                s
+            }
+        }
+    }
+
+    fn amf3_value(&self, value: &Amf3Value) -> String {
+        use Amf3Value as V;
+        match value {
+            V::Undefined => "undefined".to_owned(),
+            V::Null => "null".to_owned(),
+            V::Boolean(b) => b.to_string(),
+            V::Integer(n) => n.to_string(),
+            V::Double(n) => double(*n),
+            V::String(s) => quote(s),
+            V::XmlDocument(s) => format!("xmldoc({})", quote(s)),
+            V::Xml(s) => format!("xml({})", quote(s)),
+            V::ByteArray(bytes) => format!("bytes({})", hex::encode(bytes)),
+            V::Date { unix_time } => {
+                format!("date({}, {})", unix_time.as_secs(), unix_time.subsec_nanos())
+            }
+            V::Array {
+                assoc_entries,
+                dense_entries,
+            } => {
+                let dense = self.amf3_list(dense_entries);
+                if assoc_entries.is_empty() {
+                    dense
+                } else {
+                    let mut s = String::from("array { ");
+                    for pair in assoc_entries {
+                        s.push_str(&quote(&pair.key));
+                        s.push_str(" = ");
+                        s.push_str(&self.amf3_value(&pair.value));
+                        s.push_str(", ");
+                    }
+                    s.push_str("} ");
+                    s.push_str(&dense);
+                    s
+                }
+            }
+            V::Object {
+                class_name,
+                sealed_count,
+                entries,
+            } => {
+                let mut s = String::from("object ");
+                if let Some(name) = class_name {
+                    s.push_str(&format!("class {} ", quote(name)));
+                }
+                s.push_str(&format!("sealed {sealed_count} {{ "));
+                for pair in entries {
+                    s.push_str(&quote(&pair.key));
+                    s.push_str(" = ");
+                    s.push_str(&self.amf3_value(&pair.value));
+                    s.push_str(", ");
+                }
+                s.push('}');
+                s
+            }
+            V::IntVector { is_fixed, entries } => {
+                format!("ivec {} {}", fixed(*is_fixed), scalar_list(entries))
+            }
+            V::UintVector { is_fixed, entries } => {
+                format!("uvec {} {}", fixed(*is_fixed), scalar_list(entries))
+            }
+            V::DoubleVector { is_fixed, entries } => {
+                let items: Vec<String> = entries.iter().map(|n| double(*n)).collect();
+                format!("dvec {} [{}]", fixed(*is_fixed), items.join(", "))
+            }
+            V::ObjectVector {
+                class_name,
+                is_fixed,
+                entries,
+            } => {
+                let mut s = String::from("ovec ");
+                if let Some(name) = class_name {
+                    s.push_str(&format!("class {} ", quote(name)));
+                }
+                s.push_str(&fixed(*is_fixed));
+                s.push(' ');
+                s.push_str(&self.amf3_list(entries));
+                s
+            }
+            V::Dictionary { is_weak, entries } => {
+                let mut s = format!("dict {} {{ ", if *is_weak { "weak" } else { "strong" });
+                for (k, v) in entries {
+                    s.push_str(&self.amf3_value(k));
+                    s.push_str(" => ");
+                    s.push_str(&self.amf3_value(v));
+                    s.push_str(", ");
+                }
+                s.push('}');
+                s
+            }
+        }
+    }
+
+    fn amf3_list(&self, values: &[Amf3Value]) -> String {
+        let items: Vec<String> = values.iter().map(|v| self.amf3_value(v)).collect();
+        format!("[{}]", items.join(", "))
+    }
+}
+
+fn scalar_list<T: std::fmt::Display>(items: &[T]) -> String {
+    let items: Vec<String> = items.iter().map(ToString::to_string).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn fixed(is_fixed: bool) -> String {
+    if is_fixed { "fixed" } else { "dynamic" }.to_owned()
+}
+
+/// Format a double so a finite value always carries a decimal point or
+/// exponent and a non-finite one is spelled `nan`/`inf`/`-inf`, keeping it
+/// distinct from an integer token on the way back in.
+fn double(n: f64) -> String {
+    if n.is_finite() {
+        let s = format!("{n:?}");
+        if s.contains(['.', 'e', 'E']) {
+            s
+        } else {
+            format!("{s}.0")
+        }
+    } else if n.is_nan() {
+        "nan".to_owned()
+    } else if n > 0.0 {
+        "inf".to_owned()
+    } else {
+        "-inf".to_owned()
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// --- Lexer -----------------------------------------------------------------
+
+#[derive(Debug, PartialEq)]
+enum Tok {
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    FatArrow,
+    Str(String),
+    Word(String),
+}
+
+fn lex(src: &str) -> Result<Vec<Tok>, TextError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '{' => push(&mut tokens, &mut chars, Tok::LBrace),
+            '}' => push(&mut tokens, &mut chars, Tok::RBrace),
+            '[' => push(&mut tokens, &mut chars, Tok::LBracket),
+            ']' => push(&mut tokens, &mut chars, Tok::RBracket),
+            '(' => push(&mut tokens, &mut chars, Tok::LParen),
+            ')' => push(&mut tokens, &mut chars, Tok::RParen),
+            ',' => push(&mut tokens, &mut chars, Tok::Comma),
+            '=' => {
+                chars.next();
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    tokens.push(Tok::FatArrow);
+                } else {
+                    tokens.push(Tok::Eq);
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('"') => s.push('"'),
+                            Some('\\') => s.push('\\'),
+                            Some('n') => s.push('\n'),
+                            Some('t') => s.push('\t'),
+                            Some('r') => s.push('\r'),
+                            _ => return Err(TextError::BadEscape),
+                        },
+                        Some(c) => s.push(c),
+                        None => return Err(TextError::UnterminatedString),
+                    }
+                }
+                tokens.push(Tok::Str(s));
+            }
+            _ => {
+                let mut w = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}[](),=\"".contains(c) {
+                        break;
+                    }
+                    w.push(c);
+                    chars.next();
+                }
+                tokens.push(Tok::Word(w));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn push(tokens: &mut Vec<Tok>, chars: &mut std::iter::Peekable<std::str::Chars>, tok: Tok) {
+    chars.next();
+    tokens.push(tok);
+}
+
+// --- Parser ----------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<Tok>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<&Tok, TextError> {
+        let tok = self.tokens.get(self.pos).ok_or(TextError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(tok)
+    }
+
+    fn expect(&mut self, tok: Tok) -> Result<(), TextError> {
+        if self.next()? == &tok {
+            Ok(())
+        } else {
+            Err(TextError::Expected)
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), TextError> {
+        if self.pos == self.tokens.len() {
+            Ok(())
+        } else {
+            Err(TextError::TrailingTokens)
+        }
+    }
+
+    fn word(&mut self) -> Result<String, TextError> {
+        match self.next()? {
+            Tok::Word(w) => Ok(w.clone()),
+            _ => Err(TextError::Expected),
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Tok::Word(w)) if w == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn string(&mut self) -> Result<String, TextError> {
+        match self.next()? {
+            Tok::Str(s) => Ok(s.clone()),
+            _ => Err(TextError::Expected),
+        }
+    }
+
+    fn parse_sol(&mut self) -> Result<SolVariant, TextError> {
+        if !self.eat_word("sol") {
+            return Err(TextError::Expected);
+        }
+        let root_name = self.string()?;
+        let version = self.word()?;
+        self.expect(Tok::LBrace)?;
+        let sol = match version.as_str() {
+            "amf0" => {
+                let mut pairs = Vec::new();
+                while self.peek() != Some(&Tok::RBrace) {
+                    let key = self.string()?;
+                    self.expect(Tok::Eq)?;
+                    pairs.push(Pair {
+                        key,
+                        value: self.parse_amf0_value()?,
+                    });
+                }
+                SolVariant::Amf0(Sol::<Amf0>::new(root_name, pairs))
+            }
+            "amf3" => {
+                let mut pairs = Vec::new();
+                while self.peek() != Some(&Tok::RBrace) {
+                    let key = self.string()?;
+                    self.expect(Tok::Eq)?;
+                    pairs.push(Pair {
+                        key,
+                        value: self.parse_amf3_value()?,
+                    });
+                }
+                SolVariant::Amf3(Sol::new(root_name, pairs))
+            }
+            _ => return Err(TextError::UnknownVersion(version)),
+        };
+        self.expect(Tok::RBrace)?;
+        Ok(sol)
+    }
+
+    fn parse_amf0_value(&mut self) -> Result<Amf0Value, TextError> {
+        match self.peek() {
+            Some(Tok::Str(_)) => Ok(Amf0Value::String(self.string()?)),
+            Some(Tok::Word(w)) if w == "object" => {
+                self.pos += 1;
+                self.expect(Tok::LBrace)?;
+                let mut entries = Vec::new();
+                while self.peek() != Some(&Tok::RBrace) {
+                    let key = self.string()?;
+                    self.expect(Tok::Eq)?;
+                    let value = self.parse_amf0_value()?;
+                    entries.push(Pair { key, value });
+                    self.eat_comma();
+                }
+                self.expect(Tok::RBrace)?;
+                Ok(Amf0Value::Object(entries))
+            }
+            Some(Tok::Word(w)) if w == "true" || w == "false" => {
+                Ok(Amf0Value::Bool(self.word()? == "true"))
+            }
+            Some(Tok::Word(_)) => Ok(Amf0Value::Num(parse_double(&self.word()?)?)),
+            _ => Err(TextError::Expected),
+        }
+    }
+
+    fn parse_amf3_value(&mut self) -> Result<Amf3Value, TextError> {
+        use Amf3Value as V;
+        match self.peek() {
+            Some(Tok::Str(_)) => Ok(V::String(self.string()?)),
+            Some(Tok::LBracket) => Ok(V::Array {
+                assoc_entries: Vec::new(),
+                dense_entries: self.parse_amf3_list()?,
+            }),
+            Some(Tok::Word(w)) => {
+                let w = w.clone();
+                match w.as_str() {
+                    "null" => {
+                        self.pos += 1;
+                        Ok(V::Null)
+                    }
+                    "undefined" => {
+                        self.pos += 1;
+                        Ok(V::Undefined)
+                    }
+                    "true" | "false" => Ok(V::Boolean(self.word()? == "true")),
+                    "object" => self.parse_amf3_object(),
+                    "array" => self.parse_amf3_array(),
+                    "bytes" => {
+                        self.pos += 1;
+                        self.expect(Tok::LParen)?;
+                        let hex = self.word()?;
+                        self.expect(Tok::RParen)?;
+                        Ok(V::ByteArray(hex_decode(&hex)?))
+                    }
+                    "date" => {
+                        self.pos += 1;
+                        self.expect(Tok::LParen)?;
+                        let secs = parse_u64(&self.word()?)?;
+                        self.expect(Tok::Comma)?;
+                        let nanos = parse_u64(&self.word()?)? as u32;
+                        self.expect(Tok::RParen)?;
+                        Ok(V::Date {
+                            unix_time: Duration::new(secs, nanos),
+                        })
+                    }
+                    "xml" => Ok(V::Xml(self.parse_call_string()?)),
+                    "xmldoc" => Ok(V::XmlDocument(self.parse_call_string()?)),
+                    "ivec" => {
+                        self.pos += 1;
+                        let is_fixed = self.parse_fixed()?;
+                        Ok(V::IntVector {
+                            is_fixed,
+                            entries: self.parse_scalar_list(|w| Ok(parse_i64(w)? as i32))?,
+                        })
+                    }
+                    "uvec" => {
+                        self.pos += 1;
+                        let is_fixed = self.parse_fixed()?;
+                        Ok(V::UintVector {
+                            is_fixed,
+                            entries: self.parse_scalar_list(|w| Ok(parse_u64(w)? as u32))?,
+                        })
+                    }
+                    "dvec" => {
+                        self.pos += 1;
+                        let is_fixed = self.parse_fixed()?;
+                        Ok(V::DoubleVector {
+                            is_fixed,
+                            entries: self.parse_scalar_list(parse_double)?,
+                        })
+                    }
+                    "ovec" => self.parse_object_vector(),
+                    "dict" => self.parse_dict(),
+                    // A bare word is a number: integer if it has no fractional
+                    // part, otherwise a double.
+                    _ => {
+                        self.pos += 1;
+                        if is_double_token(&w) {
+                            Ok(V::Double(parse_double(&w)?))
+                        } else {
+                            Ok(V::Integer(parse_i64(&w)? as i32))
+                        }
+                    }
+                }
+            }
+            _ => Err(TextError::Expected),
+        }
+    }
+
+    fn parse_amf3_object(&mut self) -> Result<Amf3Value, TextError> {
+        self.pos += 1; // `object`
+        let class_name = if self.eat_word("class") {
+            Some(self.string()?)
+        } else {
+            None
+        };
+        let sealed_count = if self.eat_word("sealed") {
+            parse_u64(&self.word()?)? as usize
+        } else {
+            0
+        };
+        self.expect(Tok::LBrace)?;
+        let mut entries = Vec::new();
+        while self.peek() != Some(&Tok::RBrace) {
+            let key = self.string()?;
+            self.expect(Tok::Eq)?;
+            let value = self.parse_amf3_value()?;
+            entries.push(Pair { key, value });
+            self.eat_comma();
+        }
+        self.expect(Tok::RBrace)?;
+        Ok(Amf3Value::Object {
+            class_name,
+            sealed_count,
+            entries,
+        })
+    }
+
+    fn parse_amf3_array(&mut self) -> Result<Amf3Value, TextError> {
+        self.pos += 1; // `array`
+        self.expect(Tok::LBrace)?;
+        let mut assoc_entries = Vec::new();
+        while self.peek() != Some(&Tok::RBrace) {
+            let key = self.string()?;
+            self.expect(Tok::Eq)?;
+            let value = self.parse_amf3_value()?;
+            assoc_entries.push(Pair { key, value });
+            self.eat_comma();
+        }
+        self.expect(Tok::RBrace)?;
+        Ok(Amf3Value::Array {
+            assoc_entries,
+            dense_entries: self.parse_amf3_list()?,
+        })
+    }
+
+    fn parse_object_vector(&mut self) -> Result<Amf3Value, TextError> {
+        self.pos += 1; // `ovec`
+        let class_name = if self.eat_word("class") {
+            Some(self.string()?)
+        } else {
+            None
+        };
+        let is_fixed = self.parse_fixed()?;
+        Ok(Amf3Value::ObjectVector {
+            class_name,
+            is_fixed,
+            entries: self.parse_amf3_list()?,
+        })
+    }
+
+    fn parse_dict(&mut self) -> Result<Amf3Value, TextError> {
+        self.pos += 1; // `dict`
+        let is_weak = match self.word()?.as_str() {
+            "weak" => true,
+            "strong" => false,
+            _ => return Err(TextError::Expected),
+        };
+        self.expect(Tok::LBrace)?;
+        let mut entries = Vec::new();
+        while self.peek() != Some(&Tok::RBrace) {
+            let key = self.parse_amf3_value()?;
+            self.expect(Tok::FatArrow)?;
+            let value = self.parse_amf3_value()?;
+            entries.push((key, value));
+            self.eat_comma();
+        }
+        self.expect(Tok::RBrace)?;
+        Ok(Amf3Value::Dictionary { is_weak, entries })
+    }
+
+    fn parse_amf3_list(&mut self) -> Result<Vec<Amf3Value>, TextError> {
+        self.expect(Tok::LBracket)?;
+        let mut items = Vec::new();
+        while self.peek() != Some(&Tok::RBracket) {
+            items.push(self.parse_amf3_value()?);
+            self.eat_comma();
+        }
+        self.expect(Tok::RBracket)?;
+        Ok(items)
+    }
+
+    fn parse_scalar_list<T>(
+        &mut self,
+        parse: impl Fn(&str) -> Result<T, TextError>,
+    ) -> Result<Vec<T>, TextError> {
+        self.expect(Tok::LBracket)?;
+        let mut items = Vec::new();
+        while self.peek() != Some(&Tok::RBracket) {
+            items.push(parse(&self.word()?)?);
+            self.eat_comma();
+        }
+        self.expect(Tok::RBracket)?;
+        Ok(items)
+    }
+
+    fn parse_call_string(&mut self) -> Result<String, TextError> {
+        self.pos += 1; // keyword
+        self.expect(Tok::LParen)?;
+        let s = self.string()?;
+        self.expect(Tok::RParen)?;
+        Ok(s)
+    }
+
+    fn parse_fixed(&mut self) -> Result<bool, TextError> {
+        match self.word()?.as_str() {
+            "fixed" => Ok(true),
+            "dynamic" => Ok(false),
+            _ => Err(TextError::Expected),
+        }
+    }
+
+    fn eat_comma(&mut self) {
+        if self.peek() == Some(&Tok::Comma) {
+            self.pos += 1;
+        }
+    }
+}
+
+fn is_double_token(w: &str) -> bool {
+    w == "nan" || w == "inf" || w == "-inf" || w.contains(['.', 'e', 'E'])
+}
+
+fn parse_double(w: &str) -> Result<f64, TextError> {
+    match w {
+        "nan" => Ok(f64::NAN),
+        "inf" => Ok(f64::INFINITY),
+        "-inf" => Ok(f64::NEG_INFINITY),
+        _ => w.parse().map_err(|_| TextError::BadNumber),
+    }
+}
+
+fn parse_i64(w: &str) -> Result<i64, TextError> {
+    w.parse().map_err(|_| TextError::BadNumber)
+}
+
+fn parse_u64(w: &str) -> Result<u64, TextError> {
+    w.parse().map_err(|_| TextError::BadNumber)
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, TextError> {
+    hex::decode(s).ok_or(TextError::BadHex)
+}
+
+/// Something went wrong lexing or parsing the textual representation.
+#[derive(Debug)]
+pub enum TextError {
+    UnexpectedEnd,
+    TrailingTokens,
+    Expected,
+    UnknownVersion(String),
+    UnterminatedString,
+    BadEscape,
+    BadNumber,
+    BadHex,
+}
+
+impl std::fmt::Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            TextError::TrailingTokens => write!(f, "trailing tokens after the sol"),
+            TextError::Expected => write!(f, "unexpected token"),
+            TextError::UnknownVersion(v) => write!(f, "unknown AMF version: {v}"),
+            TextError::UnterminatedString => write!(f, "unterminated string literal"),
+            TextError::BadEscape => write!(f, "invalid escape sequence"),
+            TextError::BadNumber => write!(f, "invalid number"),
+            TextError::BadHex => write!(f, "invalid hex in bytes(…)"),
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sol_text_round_trips() {
+        use Amf3Value as V;
+        let root = vec![
+            Pair {
+                key: "blob".to_owned(),
+                value: V::ByteArray(vec![0x00, 0xde, 0xad, 0xff]),
+            },
+            Pair {
+                key: "when".to_owned(),
+                value: V::Date {
+                    unix_time: Duration::new(1_700_000_000, 500),
+                },
+            },
+            Pair {
+                key: "ints".to_owned(),
+                value: V::IntVector {
+                    is_fixed: true,
+                    entries: vec![-1, 2, 3],
+                },
+            },
+            Pair {
+                key: "uints".to_owned(),
+                value: V::UintVector {
+                    is_fixed: false,
+                    entries: vec![1, 2, 3],
+                },
+            },
+            Pair {
+                key: "speeds".to_owned(),
+                value: V::DoubleVector {
+                    is_fixed: false,
+                    entries: vec![1.5, 2.0],
+                },
+            },
+            Pair {
+                key: "bag".to_owned(),
+                value: V::Dictionary {
+                    is_weak: false,
+                    entries: vec![(V::String("k".to_owned()), V::Integer(7))],
+                },
+            },
+        ];
+        let sol = SolVariant::Amf3(Sol::new("save".to_owned(), root));
+        let text = to_text(&sol);
+        let parsed = from_text(&text).expect("should re-parse");
+        // `text -> value -> text` reproduces the input, which also pins down
+        // `sol -> text -> sol` for every variant above.
+        assert_eq!(text, to_text(&parsed));
+    }
+}